@@ -0,0 +1,207 @@
+//! Pluggable execution backends for the docking CLI.
+//!
+//! Execution used to be hard-wired to a local conda `python -m
+//! moldockpipe.cli` invocation. `ExecBackend` abstracts "how to build the
+//! command that runs the CLI" behind a trait so the same calling code in
+//! `main.rs` can target a local interpreter, a Docker image, or a remote
+//! host over SSH, selected per-call or via a project's `config/backend.json`.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::with_repo_pythonpath;
+
+pub trait ExecBackend {
+    /// Builds the `Command` that runs `moldockpipe.cli` with `args`, rooted
+    /// at `cwd` if given. Callers are free to add stdio redirection on top.
+    fn build_command(&self, args: &[String], cwd: Option<&str>) -> Command;
+
+    /// Short human-readable label, used in error messages and backend lists.
+    fn describe(&self) -> String;
+
+    /// Whether this backend runs the interpreter on this machine. Only local
+    /// backends can be served by the warm worker process.
+    fn is_local(&self) -> bool {
+        false
+    }
+}
+
+pub struct LocalCondaBackend {
+    pub python_path: String,
+}
+
+impl ExecBackend for LocalCondaBackend {
+    fn build_command(&self, args: &[String], cwd: Option<&str>) -> Command {
+        let mut command = Command::new(&self.python_path);
+        if let Some(dir) = cwd {
+            command.current_dir(dir);
+        }
+        with_repo_pythonpath(&mut command);
+        command.arg("-m").arg("moldockpipe.cli").args(args);
+        command
+    }
+
+    fn describe(&self) -> String {
+        format!("local conda ({})", self.python_path)
+    }
+
+    fn is_local(&self) -> bool {
+        true
+    }
+}
+
+pub struct DockerBackend {
+    pub image: String,
+}
+
+impl ExecBackend for DockerBackend {
+    fn build_command(&self, args: &[String], cwd: Option<&str>) -> Command {
+        let mut command = Command::new("docker");
+        command.arg("run").arg("--rm");
+        if let Some(dir) = cwd {
+            command.arg("-v").arg(format!("{}:/work", dir)).arg("-w").arg("/work");
+        }
+        command
+            .arg(&self.image)
+            .arg("python")
+            .arg("-m")
+            .arg("moldockpipe.cli")
+            .args(args);
+        command
+    }
+
+    fn describe(&self) -> String {
+        format!("Docker ({})", self.image)
+    }
+}
+
+/// `remote_project_root`, if set, is the path this project is mounted at on
+/// `host` — translated from the local `cwd` the caller passes in, since a
+/// remote host is not normally set up to mirror the local machine's absolute
+/// paths. Falls back to using `cwd` verbatim when unset, which only works if
+/// the remote host happens to mount the project at that same path.
+pub struct RemoteSshBackend {
+    pub host: String,
+    pub remote_python: String,
+    pub remote_project_root: Option<String>,
+}
+
+impl ExecBackend for RemoteSshBackend {
+    fn build_command(&self, args: &[String], cwd: Option<&str>) -> Command {
+        let mut remote_command = String::new();
+        if let Some(dir) = self.remote_project_root.as_deref().or(cwd) {
+            remote_command.push_str(&format!("cd {} && ", shell_quote(dir)));
+        }
+        remote_command.push_str(&shell_quote(&self.remote_python));
+        remote_command.push_str(" -m moldockpipe.cli");
+        for arg in args {
+            remote_command.push(' ');
+            remote_command.push_str(&shell_quote(arg));
+        }
+
+        let mut command = Command::new("ssh");
+        command.arg(&self.host).arg(remote_command);
+        command
+    }
+
+    fn describe(&self) -> String {
+        format!("remote via SSH ({})", self.host)
+    }
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Project-level backend selection, read from `config/backend.json` when a
+/// call doesn't pass an explicit `backend` argument.
+#[derive(Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum BackendConfig {
+    LocalConda { python_path: Option<String> },
+    Docker { image: String },
+    RemoteSsh {
+        host: String,
+        remote_python: Option<String>,
+        remote_project_root: Option<String>,
+    },
+}
+
+/// Builds the backend to use for a call: an explicit `config` wins, falling
+/// back to a `LocalCondaBackend` using `fallback_python` (the CLI's existing
+/// default behavior) when none is given.
+pub fn resolve(config: Option<BackendConfig>, fallback_python: String) -> Box<dyn ExecBackend> {
+    match config {
+        Some(BackendConfig::LocalConda { python_path }) => Box::new(LocalCondaBackend {
+            python_path: python_path.filter(|value| !value.trim().is_empty()).unwrap_or(fallback_python),
+        }),
+        Some(BackendConfig::Docker { image }) => Box::new(DockerBackend { image }),
+        Some(BackendConfig::RemoteSsh {
+            host,
+            remote_python,
+            remote_project_root,
+        }) => Box::new(RemoteSshBackend {
+            host,
+            remote_python: remote_python.unwrap_or(fallback_python),
+            remote_project_root,
+        }),
+        None => Box::new(LocalCondaBackend { python_path: fallback_python }),
+    }
+}
+
+/// Reads `{project_dir}/config/backend.json`, if present, as the project's
+/// default backend.
+pub fn load_project_config(project_dir: &str) -> Option<BackendConfig> {
+    let path = PathBuf::from(project_dir).join("config").join("backend.json");
+    let contents = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+#[derive(Serialize)]
+pub struct BackendInfo {
+    pub id: String,
+    pub description: String,
+}
+
+fn is_on_path(binary: &str) -> bool {
+    let Some(path_var) = std::env::var_os("PATH") else {
+        return false;
+    };
+    std::env::split_paths(&path_var).any(|dir| dir.join(binary).exists() || dir.join(format!("{}.exe", binary)).exists())
+}
+
+/// Lists every backend this machine looks able to run right now: the local
+/// conda interpreter (always listed, falling back to `python` on PATH when no
+/// conda install is detected), Docker (if on PATH), and SSH (if on PATH).
+/// Replaces the old `detect_python_path_cmd`, which could only ever report
+/// one local interpreter.
+pub fn detect_backends() -> Vec<BackendInfo> {
+    let mut backends = Vec::new();
+
+    let python_description = match crate::detect_python_path() {
+        Some(path) => format!("Local conda Python ({})", path.to_string_lossy()),
+        None => "Local conda Python (not detected, will try `python` on PATH)".to_string(),
+    };
+    backends.push(BackendInfo {
+        id: "local-conda".to_string(),
+        description: python_description,
+    });
+
+    if is_on_path("docker") {
+        backends.push(BackendInfo {
+            id: "docker".to_string(),
+            description: "Docker (runs a published moldockpipe image)".to_string(),
+        });
+    }
+
+    if is_on_path("ssh") {
+        backends.push(BackendInfo {
+            id: "remote-ssh".to_string(),
+            description: "Remote host over SSH".to_string(),
+        });
+    }
+
+    backends
+}
@@ -0,0 +1,207 @@
+//! Persistent job-state model for docking runs.
+//!
+//! Unlike the transient `JOB_STATUS` map in `main.rs` (which only knows
+//! "unknown / running / finished-with-code" and forgets everything on
+//! restart), a [`JobRecord`] carries enough metadata to reconstruct a job's
+//! history and is written to `state/jobs/{job_id}.json` in the owning
+//! project on every state transition, so it survives the app being closed.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+    Canceled,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct JobRecord {
+    pub job_id: u64,
+    pub project_dir: String,
+    pub args: Vec<String>,
+    pub cwd: Option<String>,
+    pub state: JobState,
+    pub pid: Option<u32>,
+    pub watcher_pid: Option<u32>,
+    pub exit_code: Option<i32>,
+    pub started_at: u64,
+    pub ended_at: Option<u64>,
+}
+
+static JOBS: OnceLock<Mutex<HashMap<u64, JobRecord>>> = OnceLock::new();
+
+fn jobs_map() -> &'static Mutex<HashMap<u64, JobRecord>> {
+    JOBS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+pub fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+fn jobs_dir(project_dir: &str) -> PathBuf {
+    PathBuf::from(project_dir).join("state").join("jobs")
+}
+
+fn job_file(project_dir: &str, job_id: u64) -> PathBuf {
+    jobs_dir(project_dir).join(format!("{}.json", job_id))
+}
+
+fn persist(record: &JobRecord) {
+    let dir = jobs_dir(&record.project_dir);
+    if fs::create_dir_all(&dir).is_err() {
+        return;
+    }
+    if let Ok(json) = serde_json::to_string_pretty(record) {
+        let _ = fs::write(job_file(&record.project_dir, record.job_id), json);
+    }
+}
+
+/// Registers a new job as `Queued` and persists it immediately.
+pub fn create(job_id: u64, project_dir: String, args: Vec<String>, cwd: Option<String>) -> JobRecord {
+    let record = JobRecord {
+        job_id,
+        project_dir,
+        args,
+        cwd,
+        state: JobState::Queued,
+        pid: None,
+        watcher_pid: None,
+        exit_code: None,
+        started_at: unix_timestamp(),
+        ended_at: None,
+    };
+    if let Ok(mut map) = jobs_map().lock() {
+        map.insert(job_id, record.clone());
+    }
+    persist(&record);
+    record
+}
+
+/// Applies `mutate` to the in-memory record (if any) and re-persists it, so
+/// every transition (queued -> running -> completed/failed/canceled) leaves
+/// an up-to-date file on disk.
+pub fn transition(job_id: u64, mutate: impl FnOnce(&mut JobRecord)) {
+    let updated = {
+        let mut map = match jobs_map().lock() {
+            Ok(map) => map,
+            Err(_) => return,
+        };
+        match map.get_mut(&job_id) {
+            Some(record) => {
+                mutate(record);
+                Some(record.clone())
+            }
+            None => None,
+        }
+    };
+    if let Some(record) = updated {
+        persist(&record);
+    }
+}
+
+pub fn get(job_id: u64) -> Option<JobRecord> {
+    jobs_map().lock().ok().and_then(|map| map.get(&job_id).cloned())
+}
+
+/// Loads any job files for `project_dir` that aren't already in memory
+/// (e.g. left over from a previous app session) so they show up in
+/// `list_run_jobs`/`get_run_job_status`.
+pub fn reload_project(project_dir: &str) {
+    let dir = jobs_dir(project_dir);
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    let mut map = match jobs_map().lock() {
+        Ok(map) => map,
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        if let Ok(contents) = fs::read_to_string(&path) {
+            if let Ok(record) = serde_json::from_str::<JobRecord>(&contents) {
+                map.entry(record.job_id).or_insert(record);
+            }
+        }
+    }
+}
+
+pub fn list_for_project(project_dir: &str) -> Vec<JobRecord> {
+    reload_project(project_dir);
+    jobs_map()
+        .lock()
+        .map(|map| {
+            let mut records: Vec<JobRecord> = map
+                .values()
+                .filter(|record| record.project_dir == project_dir)
+                .cloned()
+                .collect();
+            records.sort_by_key(|record| record.started_at);
+            records
+        })
+        .unwrap_or_default()
+}
+
+/// Highest `job_id` currently in memory, or 0 if none are loaded. Used to
+/// seed the job id counter at startup so freshly created jobs never collide
+/// with ids reloaded from a previous session.
+pub fn max_job_id() -> u64 {
+    jobs_map()
+        .lock()
+        .map(|map| map.keys().copied().max().unwrap_or(0))
+        .unwrap_or(0)
+}
+
+fn pid_is_alive(pid: u32) -> bool {
+    if cfg!(target_os = "windows") {
+        Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {}", pid)])
+            .output()
+            .map(|output| String::from_utf8_lossy(&output.stdout).contains(&pid.to_string()))
+            .unwrap_or(false)
+    } else {
+        Command::new("kill").args(["-0", &pid.to_string()]).status().map(|status| status.success()).unwrap_or(false)
+    }
+}
+
+/// Any record still `Queued`/`Running` after a reload was left that way by an
+/// app session that never got to record its outcome (a crash, or the process
+/// being killed outright). Such a record is marked `Failed` unless its `pid`
+/// is still alive. Only liveness is checked: a `pid` the OS has since
+/// reassigned to an unrelated process keeps the record `Running`.
+pub fn reconcile_stale_records() {
+    let stale_ids: Vec<u64> = jobs_map()
+        .lock()
+        .map(|map| {
+            map.values()
+                .filter(|record| matches!(record.state, JobState::Queued | JobState::Running))
+                .filter(|record| !record.pid.map(pid_is_alive).unwrap_or(false))
+                .map(|record| record.job_id)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    for job_id in stale_ids {
+        transition(job_id, |job| {
+            job.state = JobState::Failed;
+            job.exit_code = None;
+            job.ended_at = Some(unix_timestamp());
+        });
+    }
+}
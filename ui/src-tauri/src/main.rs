@@ -1,30 +1,38 @@
-#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
-
+#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
+
+mod backend;
+mod jobs;
+mod pipeline;
+mod watch;
+mod worker;
+
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
 use std::fs;
+use std::io::{BufRead, BufReader, Read};
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Stdio};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Mutex, OnceLock};
 use std::thread;
 use std::time::Duration;
-
-#[derive(Serialize)]
-struct ProjectItem {
-    name: String,
-    path: String,
-    source: String,
-}
-
-#[derive(Serialize)]
-struct RunResult {
-    ok: bool,
-    stdout: String,
-    stderr: String,
-    code: i32,
-}
-
+use tauri::{AppHandle, Emitter};
+
+#[derive(Serialize, Deserialize)]
+struct ProjectItem {
+    name: String,
+    path: String,
+    source: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct RunResult {
+    ok: bool,
+    stdout: String,
+    stderr: String,
+    code: i32,
+}
+
 #[derive(Serialize, Deserialize)]
 struct CsvPreview {
     headers: Vec<String>,
@@ -45,30 +53,111 @@ struct RunJobStatus {
     exit_code: Option<i32>,
 }
 
+/// Bounded buffer of the most recent output lines for a job, so a frontend
+/// that subscribes to the `run://{job_id}/...` events late can still fetch
+/// the backlog instead of starting from a blank console.
+const OUTPUT_RING_CAPACITY: usize = 200;
+
+#[derive(Default)]
+struct RingBuffer {
+    lines: VecDeque<String>,
+}
+
+impl RingBuffer {
+    fn push(&mut self, line: String) {
+        if self.lines.len() >= OUTPUT_RING_CAPACITY {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+    }
+
+    fn snapshot(&self) -> Vec<String> {
+        self.lines.iter().cloned().collect()
+    }
+}
+
+#[derive(Default)]
+struct JobOutput {
+    stdout: RingBuffer,
+    stderr: RingBuffer,
+}
+
+#[derive(Serialize)]
+struct RunJobOutput {
+    stdout: Vec<String>,
+    stderr: Vec<String>,
+}
+
+#[derive(Clone, Serialize)]
+struct RunOutputEvent {
+    job_id: u64,
+    line: String,
+}
+
 static JOB_COUNTER: AtomicU64 = AtomicU64::new(1);
 static JOB_STATUS: OnceLock<Mutex<std::collections::HashMap<u64, Option<i32>>>> = OnceLock::new();
+static JOB_OUTPUT: OnceLock<Mutex<std::collections::HashMap<u64, JobOutput>>> = OnceLock::new();
+static JOB_RESULT: OnceLock<Mutex<std::collections::HashMap<u64, RunResult>>> = OnceLock::new();
 
 fn job_status_map() -> &'static Mutex<std::collections::HashMap<u64, Option<i32>>> {
     JOB_STATUS.get_or_init(|| Mutex::new(std::collections::HashMap::new()))
 }
-fn find_repo_root() -> Option<PathBuf> {
-    let mut current = std::env::current_dir().ok()?;
-    for _ in 0..6 {
-        if current.join("pyproject.toml").exists() {
-            return Some(current);
-        }
-        if !current.pop() {
-            break;
-        }
-    }
-    None
-}
-
+
+fn job_output_map() -> &'static Mutex<std::collections::HashMap<u64, JobOutput>> {
+    JOB_OUTPUT.get_or_init(|| Mutex::new(std::collections::HashMap::new()))
+}
+
+fn job_result_map() -> &'static Mutex<std::collections::HashMap<u64, RunResult>> {
+    JOB_RESULT.get_or_init(|| Mutex::new(std::collections::HashMap::new()))
+}
+
+/// Reads `reader` line by line, pushing each line into the job's ring buffer
+/// and forwarding it to the frontend as a `run://{job_id}/{stream}` event, so
+/// long docking runs show live output instead of only a final summary. The
+/// full accumulated text is returned once the stream closes so the caller can
+/// still build a complete `RunResult` on process exit.
+fn stream_job_output<R: Read + Send + 'static>(
+    app: AppHandle,
+    job_id: u64,
+    stream: &'static str,
+    reader: R,
+) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut accumulated = String::new();
+        for line in BufReader::new(reader).lines().flatten() {
+            accumulated.push_str(&line);
+            accumulated.push('\n');
+            if let Ok(mut map) = job_output_map().lock() {
+                let entry = map.entry(job_id).or_default();
+                match stream {
+                    "stdout" => entry.stdout.push(line.clone()),
+                    _ => entry.stderr.push(line.clone()),
+                }
+            }
+            let _ = app.emit(&format!("run://{}/{}", job_id, stream), RunOutputEvent { job_id, line });
+        }
+        accumulated
+    })
+}
+
+fn find_repo_root() -> Option<PathBuf> {
+    let mut current = std::env::current_dir().ok()?;
+    for _ in 0..6 {
+        if current.join("pyproject.toml").exists() {
+            return Some(current);
+        }
+        if !current.pop() {
+            break;
+        }
+    }
+    None
+}
+
 fn normalize_path_case(path: &Path) -> String {
     path.to_string_lossy().replace('\\', "/")
 }
 
-fn with_repo_pythonpath(command: &mut Command) {
+pub(crate) fn with_repo_pythonpath(command: &mut Command) {
     if let Some(repo_root) = find_repo_root() {
         let repo_str = repo_root.to_string_lossy().to_string();
         let merged = match std::env::var("PYTHONPATH") {
@@ -101,7 +190,7 @@ fn write_stop_signal(stop_file: &Path, phase: &str, message: &str) {
     let _ = fs::write(stop_file, payload);
 }
 
-fn detect_python_path() -> Option<PathBuf> {
+pub(crate) fn detect_python_path() -> Option<PathBuf> {
     if let Ok(prefix) = std::env::var("CONDA_PREFIX") {
         let candidate = PathBuf::from(prefix).join("python.exe");
         if candidate.exists() {
@@ -138,75 +227,99 @@ fn detect_python_path() -> Option<PathBuf> {
 }
 
 #[tauri::command]
-fn discover_projects() -> Result<Vec<ProjectItem>, String> {
-    let mut project_dirs: Vec<(PathBuf, String)> = Vec::new();
-
-    if let Some(repo_root) = find_repo_root() {
-        let root_projects = repo_root.join("projects");
-        if root_projects.exists() {
-            project_dirs.push((root_projects, "repo projects".to_string()));
-        }
-    }
-
-    if let Some(home_dir) = std::env::var_os("USERPROFILE") {
-        let docs_projects = PathBuf::from(home_dir).join("Documents").join("MolDockPipeV2").join("Projects");
-        if docs_projects.exists() {
-            project_dirs.push((docs_projects, "Documents".to_string()));
-        }
-    }
-
-    let mut seen = HashSet::new();
-    let mut results = Vec::new();
-
-    for (base, source) in project_dirs {
-        let entries = fs::read_dir(base).map_err(|err| err.to_string())?;
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if path.is_dir() && path.join("config").exists() {
-                let normalized = normalize_path_case(&path).to_ascii_lowercase();
-                if !seen.insert(normalized) {
-                    continue;
-                }
-                let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
-                results.push(ProjectItem {
-                    name,
-                    path: normalize_path_case(&path),
-                    source: source.clone(),
-                });
-            }
-        }
-    }
-
-    results.sort_by(|a, b| a.name.cmp(&b.name));
-    Ok(results)
-}
-
+fn discover_projects(python_path: Option<String>) -> Result<Vec<ProjectItem>, String> {
+    let python = python_path
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "python".to_string());
+    if let Ok(value) = worker::call(&python, "discover_projects", serde_json::Value::Null) {
+        if let Ok(results) = serde_json::from_value::<Vec<ProjectItem>>(value) {
+            return Ok(results);
+        }
+    }
+
+    discover_projects_local()
+}
+
+/// Local filesystem scan backing [`discover_projects`], also used at startup
+/// to find every known project without paying for a worker round-trip.
+fn discover_projects_local() -> Result<Vec<ProjectItem>, String> {
+    let mut project_dirs: Vec<(PathBuf, String)> = Vec::new();
+
+    if let Some(repo_root) = find_repo_root() {
+        let root_projects = repo_root.join("projects");
+        if root_projects.exists() {
+            project_dirs.push((root_projects, "repo projects".to_string()));
+        }
+    }
+
+    if let Some(home_dir) = std::env::var_os("USERPROFILE") {
+        let docs_projects = PathBuf::from(home_dir).join("Documents").join("MolDockPipeV2").join("Projects");
+        if docs_projects.exists() {
+            project_dirs.push((docs_projects, "Documents".to_string()));
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let mut results = Vec::new();
+
+    for (base, source) in project_dirs {
+        let entries = fs::read_dir(base).map_err(|err| err.to_string())?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() && path.join("config").exists() {
+                let normalized = normalize_path_case(&path).to_ascii_lowercase();
+                if !seen.insert(normalized) {
+                    continue;
+                }
+                let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+                results.push(ProjectItem {
+                    name,
+                    path: normalize_path_case(&path),
+                    source: source.clone(),
+                });
+            }
+        }
+    }
+
+    results.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(results)
+}
+
 #[tauri::command]
 fn run_moldock(
     args: Vec<String>,
     cwd: Option<String>,
     python_path: Option<String>,
+    backend: Option<backend::BackendConfig>,
 ) -> Result<RunResult, String> {
     let python = python_path
         .filter(|value| !value.trim().is_empty())
         .unwrap_or_else(|| "python".to_string());
+    let backend_config = backend.or_else(|| cwd.as_deref().and_then(backend::load_project_config));
+    let exec_backend = backend::resolve(backend_config, python.clone());
 
-    let mut command = Command::new(&python);
-    if let Some(run_dir) = cwd {
-        // Run relative to the project directory so the CLI can resolve project files.
-        command.current_dir(run_dir);
+    // Prefer the warm worker process (avoids a fresh interpreter/import cost
+    // per call) and only fall back to a one-shot spawn when it is unavailable.
+    // The worker always runs locally, so only the local backend can use it.
+    if exec_backend.is_local() {
+        let params = serde_json::json!({ "args": args, "cwd": cwd });
+        if let Ok(value) = worker::call(&python, "run", params) {
+            if let Ok(result) = serde_json::from_value::<RunResult>(value) {
+                return Ok(result);
+            }
+        }
     }
-    with_repo_pythonpath(&mut command);
-    command.arg("-m").arg("moldockpipe.cli").args(args);
 
+    let mut command = exec_backend.build_command(&args, cwd.as_deref());
     let output = command.output().map_err(|err| {
         format!(
-            "Could not launch Python CLI. Verify Python path and module availability (moldockpipe.cli). Details: {}",
+            "Could not launch {} backend. Verify it is reachable and moldockpipe.cli is available. Details: {}",
+            exec_backend.describe(),
             err
         )
     })?;
-
-    let code = output.status.code().unwrap_or(1);
+
+    let code = output.status.code().unwrap_or(1);
     Ok(RunResult {
         ok: output.status.success(),
         stdout: String::from_utf8_lossy(&output.stdout).to_string(),
@@ -216,40 +329,77 @@ fn run_moldock(
 }
 
 #[tauri::command]
-fn run_moldock_async(
+pub(crate) fn run_moldock_async(
+    app: AppHandle,
     args: Vec<String>,
     cwd: Option<String>,
     python_path: Option<String>,
+    backend: Option<backend::BackendConfig>,
 ) -> Result<RunJob, String> {
     let python = python_path
         .filter(|value| !value.trim().is_empty())
         .unwrap_or_else(|| "python".to_string());
+    let backend_config = backend.or_else(|| cwd.as_deref().and_then(backend::load_project_config));
+    let exec_backend = backend::resolve(backend_config, python.clone());
     let job_id = JOB_COUNTER.fetch_add(1, Ordering::Relaxed);
     if let Ok(mut map) = job_status_map().lock() {
         map.insert(job_id, None);
     }
+    if let Ok(mut map) = job_output_map().lock() {
+        map.insert(job_id, JobOutput::default());
+    }
 
     let is_run_command = args.first().map(|v| v == "run").unwrap_or(false);
     if !is_run_command {
-        let mut command = Command::new(&python);
-        if let Some(run_dir) = cwd {
-            command.current_dir(run_dir);
-        }
-        with_repo_pythonpath(&mut command);
-        command.arg("-m").arg("moldockpipe.cli").args(args);
+        let project_dir = cwd.clone().unwrap_or_else(|| ".".to_string());
+        jobs::create(job_id, project_dir.clone(), args.clone(), cwd.clone());
+
+        let mut command = exec_backend.build_command(&args, cwd.as_deref());
+        command.stdout(Stdio::piped()).stderr(Stdio::piped());
         let mut child = command.spawn().map_err(|err| {
             format!(
-                "Could not launch Python CLI. Verify Python path and module availability (moldockpipe.cli). Details: {}",
+                "Could not launch {} backend. Verify it is reachable and moldockpipe.cli is available. Details: {}",
+                exec_backend.describe(),
                 err
             )
         })?;
         let pid = child.id();
+        jobs::transition(job_id, |job| {
+            job.state = jobs::JobState::Running;
+            job.pid = Some(pid);
+        });
         let job_id_copy = job_id;
+        let stdout_reader = stream_job_output(app.clone(), job_id, "stdout", child.stdout.take().expect("piped stdout"));
+        let stderr_reader = stream_job_output(app.clone(), job_id, "stderr", child.stderr.take().expect("piped stderr"));
         thread::spawn(move || {
             let code = child.wait().ok().and_then(|s| s.code());
+            let stdout = stdout_reader.join().unwrap_or_default();
+            let stderr = stderr_reader.join().unwrap_or_default();
+            if let Ok(mut map) = job_result_map().lock() {
+                map.insert(
+                    job_id_copy,
+                    RunResult {
+                        ok: code == Some(0),
+                        stdout,
+                        stderr,
+                        code: code.unwrap_or(1),
+                    },
+                );
+            }
             if let Ok(mut map) = job_status_map().lock() {
                 map.insert(job_id_copy, code);
             }
+            jobs::transition(job_id_copy, |job| {
+                if job.state != jobs::JobState::Canceled {
+                    job.state = if code == Some(0) {
+                        jobs::JobState::Completed
+                    } else {
+                        jobs::JobState::Failed
+                    };
+                    job.exit_code = code;
+                    job.ended_at = Some(jobs::unix_timestamp());
+                }
+            });
         });
         return Ok(RunJob {
             job_id,
@@ -264,95 +414,161 @@ fn run_moldock_async(
     if stop_file.exists() {
         let _ = fs::remove_file(&stop_file);
     }
+    jobs::create(job_id, project_dir.clone(), args.clone(), cwd.clone());
 
-    let watcher_run_id = format!("watch_{}", JOB_COUNTER.load(Ordering::Relaxed));
-    let mut watcher = Command::new(&python);
-    watcher
-        .arg("-m")
-        .arg("moldockpipe.progress_watcher")
-        .arg("--project")
-        .arg(&project_dir)
-        .arg("--run-id")
-        .arg(&watcher_run_id)
-        .arg("--interval-ms")
-        .arg("700");
-    watcher.current_dir(&project_dir);
-    with_repo_pythonpath(&mut watcher);
-    let mut watcher_child = watcher.spawn().map_err(|err| {
-        format!(
-            "Could not launch Python progress watcher. Verify Python path and module availability (moldockpipe.progress_watcher). Details: {}",
-            err
-        )
-    })?;
+    // The progress watcher polls `state/progress.json` on the local
+    // filesystem, which only the local backend writes to directly; for
+    // Docker/SSH backends the docking process runs on a different
+    // filesystem (container or remote host), so a local watcher would just
+    // poll a file that never changes and the stop-file it relies on for
+    // cancellation would never be seen by the run. Only start it for the
+    // local backend rather than failing silently for the others.
+    let mut watcher_child: Option<std::process::Child> = if exec_backend.is_local() {
+        let watcher_run_id = format!("watch_{}", JOB_COUNTER.load(Ordering::Relaxed));
+        let mut watcher = Command::new(&python);
+        watcher
+            .arg("-m")
+            .arg("moldockpipe.progress_watcher")
+            .arg("--project")
+            .arg(&project_dir)
+            .arg("--run-id")
+            .arg(&watcher_run_id)
+            .arg("--interval-ms")
+            .arg("700");
+        watcher.current_dir(&project_dir);
+        with_repo_pythonpath(&mut watcher);
+        Some(watcher.spawn().map_err(|err| {
+            format!(
+                "Could not launch Python progress watcher. Verify Python path and module availability (moldockpipe.progress_watcher). Details: {}",
+                err
+            )
+        })?)
+    } else {
+        None
+    };
 
-    let mut command = Command::new(&python);
-    if let Some(run_dir) = cwd {
-        // Run relative to the project directory so the CLI can resolve project files.
-        command.current_dir(run_dir);
-    }
-    with_repo_pythonpath(&mut command);
-    command.arg("-m").arg("moldockpipe.cli").args(args);
+    let mut command = exec_backend.build_command(&args, cwd.as_deref());
+    command.stdout(Stdio::piped()).stderr(Stdio::piped());
 
     let mut child = match command.spawn() {
         Ok(c) => c,
         Err(err) => {
-            let _ = watcher_child.kill();
-            let _ = watcher_child.wait();
+            if let Some(mut watcher_child) = watcher_child {
+                let _ = watcher_child.kill();
+                let _ = watcher_child.wait();
+            }
             return Err(format!(
-                "Could not launch Python CLI. Verify Python path and module availability (moldockpipe.cli). Details: {}",
+                "Could not launch {} backend. Verify it is reachable and moldockpipe.cli is available. Details: {}",
+                exec_backend.describe(),
                 err
             ));
         }
     };
     let pid = child.id();
-    let watcher_pid = watcher_child.id();
+    let watcher_pid = watcher_child.as_ref().map(std::process::Child::id);
+    jobs::transition(job_id, |job| {
+        job.state = jobs::JobState::Running;
+        job.pid = Some(pid);
+        job.watcher_pid = watcher_pid;
+    });
+    let stdout_reader = stream_job_output(app.clone(), job_id, "stdout", child.stdout.take().expect("piped stdout"));
+    let stderr_reader = stream_job_output(app.clone(), job_id, "stderr", child.stderr.take().expect("piped stderr"));
 
     // Detach coordinator thread so UI stays responsive.
     thread::spawn(move || {
         let result = child.wait();
-        let (phase, message): (&str, String) = match result {
+        let stdout = stdout_reader.join().unwrap_or_default();
+        let stderr = stderr_reader.join().unwrap_or_default();
+        let (phase, message, code): (&str, String, i32) = match result {
             Ok(status) => {
                 let code = status.code().unwrap_or(1);
+                if let Ok(mut map) = job_result_map().lock() {
+                    map.insert(
+                        job_id,
+                        RunResult {
+                            ok: status.success(),
+                            stdout,
+                            stderr,
+                            code,
+                        },
+                    );
+                }
                 if let Ok(mut map) = job_status_map().lock() {
                     map.insert(job_id, Some(code));
                 }
                 if code == 0 || code == 2 {
-                    ("completed", String::new())
+                    ("completed", String::new(), code)
                 } else {
-                    ("failed", format!("runner_exit_code={}", code))
+                    ("failed", format!("runner_exit_code={}", code), code)
                 }
             }
             Err(_) => {
+                if let Ok(mut map) = job_result_map().lock() {
+                    map.insert(
+                        job_id,
+                        RunResult {
+                            ok: false,
+                            stdout,
+                            stderr,
+                            code: 1,
+                        },
+                    );
+                }
                 if let Ok(mut map) = job_status_map().lock() {
                     map.insert(job_id, Some(1));
                 }
-                ("failed", "runner_wait_failed".to_string())
+                ("failed", "runner_wait_failed".to_string(), 1)
             }
         };
+        // A cancellation already wrote its own Canceled transition; don't
+        // clobber it with Completed/Failed just because the killed process
+        // happened to exit in the meantime.
+        jobs::transition(job_id, |job| {
+            if job.state != jobs::JobState::Canceled {
+                job.state = if phase == "completed" {
+                    jobs::JobState::Completed
+                } else {
+                    jobs::JobState::Failed
+                };
+                job.exit_code = Some(code);
+                job.ended_at = Some(jobs::unix_timestamp());
+            }
+        });
         write_stop_signal(&stop_file, phase, &message);
 
-        for _ in 0..20 {
-            match watcher_child.try_wait() {
-                Ok(Some(_)) => break,
-                Ok(None) => thread::sleep(Duration::from_millis(100)),
-                Err(_) => break,
+        if let Some(mut watcher_child) = watcher_child {
+            for _ in 0..20 {
+                match watcher_child.try_wait() {
+                    Ok(Some(_)) => break,
+                    Ok(None) => thread::sleep(Duration::from_millis(100)),
+                    Err(_) => break,
+                }
+            }
+            if let Ok(None) = watcher_child.try_wait() {
+                let _ = watcher_child.kill();
+                let _ = watcher_child.wait();
             }
-        }
-        if let Ok(None) = watcher_child.try_wait() {
-            let _ = watcher_child.kill();
-            let _ = watcher_child.wait();
         }
     });
 
     Ok(RunJob {
         job_id,
         pid: Some(pid),
-        watcher_pid: Some(watcher_pid),
+        watcher_pid,
     })
 }
 
 #[tauri::command]
 fn get_run_job_status(job_id: u64) -> Result<RunJobStatus, String> {
+    if let Some(record) = jobs::get(job_id) {
+        let running = matches!(record.state, jobs::JobState::Queued | jobs::JobState::Running);
+        return Ok(RunJobStatus {
+            found: true,
+            running,
+            exit_code: record.exit_code,
+        });
+    }
+
     let map = job_status_map()
         .lock()
         .map_err(|_| "Failed to lock job status map.".to_string())?;
@@ -376,10 +592,154 @@ fn get_run_job_status(job_id: u64) -> Result<RunJobStatus, String> {
 }
 
 #[tauri::command]
-fn detect_python_path_cmd() -> Result<String, String> {
-    Ok(detect_python_path()
-        .map(|path| path.to_string_lossy().to_string())
-        .unwrap_or_else(|| "python".to_string()))
+fn cancel_run_job(job_id: u64) -> Result<(), String> {
+    let record = jobs::get(job_id).ok_or_else(|| "Unknown job id.".to_string())?;
+
+    if let Some(pid) = record.pid {
+        kill_pid(pid);
+    }
+    if let Some(watcher_pid) = record.watcher_pid {
+        kill_pid(watcher_pid);
+    }
+
+    let stop_file = PathBuf::from(&record.project_dir).join("state").join("stop_progress_watcher");
+    write_stop_signal(&stop_file, "canceled", "canceled_by_user");
+
+    jobs::transition(job_id, |job| {
+        job.state = jobs::JobState::Canceled;
+        job.ended_at = Some(jobs::unix_timestamp());
+    });
+    if let Ok(mut map) = job_status_map().lock() {
+        map.insert(job_id, Some(1));
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+fn list_run_jobs(project_dir: String) -> Result<Vec<jobs::JobRecord>, String> {
+    Ok(jobs::list_for_project(&project_dir))
+}
+
+fn kill_pid(pid: u32) {
+    if cfg!(target_os = "windows") {
+        let _ = Command::new("taskkill").args(["/PID", &pid.to_string(), "/T", "/F"]).output();
+    } else {
+        let _ = Command::new("kill").arg(pid.to_string()).output();
+    }
+}
+
+#[tauri::command]
+fn get_run_job_output(job_id: u64) -> Result<RunJobOutput, String> {
+    let map = job_output_map()
+        .lock()
+        .map_err(|_| "Failed to lock job output map.".to_string())?;
+    match map.get(&job_id) {
+        Some(output) => Ok(RunJobOutput {
+            stdout: output.stdout.snapshot(),
+            stderr: output.stderr.snapshot(),
+        }),
+        None => Ok(RunJobOutput {
+            stdout: Vec::new(),
+            stderr: Vec::new(),
+        }),
+    }
+}
+
+/// Returns the final accumulated `RunResult` for `job_id` once the process
+/// has exited, so a run with more output than the ring buffer retains can
+/// still be read back in full. `None` while the job is still running or
+/// unknown, and always `None` for `run_pipeline_script` jobs, which record no
+/// `RunResult`.
+#[tauri::command]
+fn get_run_job_result(job_id: u64) -> Result<Option<RunResult>, String> {
+    let map = job_result_map()
+        .lock()
+        .map_err(|_| "Failed to lock job result map.".to_string())?;
+    Ok(map.get(&job_id).cloned())
+}
+
+#[tauri::command]
+fn worker_call(
+    method: String,
+    params: serde_json::Value,
+    python_path: Option<String>,
+) -> Result<serde_json::Value, String> {
+    let python = python_path
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "python".to_string());
+    worker::call(&python, &method, params)
+}
+
+#[tauri::command]
+fn watch_project(app: AppHandle, project_dir: String, args: Vec<String>) -> Result<(), String> {
+    watch::start(app, project_dir, args)
+}
+
+#[tauri::command]
+fn run_pipeline_script(
+    project_dir: String,
+    script_path: Option<String>,
+    python_path: Option<String>,
+) -> Result<RunJob, String> {
+    let python = python_path
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "python".to_string());
+    let job_id = JOB_COUNTER.fetch_add(1, Ordering::Relaxed);
+    if let Ok(mut map) = job_status_map().lock() {
+        map.insert(job_id, None);
+    }
+    jobs::create(job_id, project_dir.clone(), vec!["pipeline".to_string()], Some(project_dir.clone()));
+    jobs::transition(job_id, |job| job.state = jobs::JobState::Running);
+
+    let stop_file = PathBuf::from(&project_dir).join("state").join("stop_progress_watcher");
+    if stop_file.exists() {
+        let _ = fs::remove_file(&stop_file);
+    }
+
+    let script_path_buf = script_path.map(PathBuf::from);
+    let project_dir_thread = project_dir.clone();
+    thread::spawn(move || {
+        let result = pipeline::run_script(&python, &project_dir_thread, script_path_buf.as_deref());
+        let (code, phase, message) = match result {
+            Ok(()) => (0, "completed", String::new()),
+            Err(err) => (1, "failed", err),
+        };
+        if let Ok(mut map) = job_status_map().lock() {
+            map.insert(job_id, Some(code));
+        }
+        // A cancellation already wrote its own Canceled transition; don't
+        // clobber it with Completed/Failed just because the script's next
+        // `run(...)` step happened to notice the stop file and unwind first.
+        jobs::transition(job_id, |job| {
+            if job.state != jobs::JobState::Canceled {
+                job.state = if phase == "completed" {
+                    jobs::JobState::Completed
+                } else {
+                    jobs::JobState::Failed
+                };
+                job.exit_code = Some(code);
+                job.ended_at = Some(jobs::unix_timestamp());
+            }
+        });
+        write_stop_signal(&stop_file, phase, &message);
+    });
+
+    Ok(RunJob {
+        job_id,
+        pid: None,
+        watcher_pid: None,
+    })
+}
+
+#[tauri::command]
+fn stop_watch_project(project_dir: String) -> Result<(), String> {
+    watch::stop(&project_dir)
+}
+
+#[tauri::command]
+fn detect_backends() -> Result<Vec<backend::BackendInfo>, String> {
+    Ok(backend::detect_backends())
 }
 
 #[tauri::command]
@@ -410,12 +770,12 @@ fn open_in_explorer(path: String) -> Result<(), String> {
         cmd.arg(resolved);
         cmd
     };
-
-    command.spawn().map_err(|err| err.to_string())?;
-    Ok(())
-}
-
-#[tauri::command]
+
+    command.spawn().map_err(|err| err.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
 fn read_text_file(path: String) -> Result<String, String> {
     fs::read_to_string(path).map_err(|err| err.to_string())
 }
@@ -435,40 +795,77 @@ fn read_progress_file(project_dir: String) -> Result<String, String> {
     fs::read_to_string(progress_path).map_err(|err| err.to_string())
 }
 
-#[tauri::command]
-fn read_csv_preview(path: String, max_rows: usize) -> Result<CsvPreview, String> {
-    let mut reader = csv::Reader::from_path(path).map_err(|err| err.to_string())?;
-    let headers = reader
-        .headers()
-        .map_err(|err| err.to_string())?
-        .iter()
-        .map(std::string::ToString::to_string)
-        .collect::<Vec<String>>();
-
-    let rows = reader
-        .records()
-        .take(max_rows)
-        .flatten()
-        .map(|record| record.iter().map(std::string::ToString::to_string).collect::<Vec<String>>())
-        .collect::<Vec<Vec<String>>>();
-
-    Ok(CsvPreview { headers, rows })
+#[tauri::command]
+fn read_csv_preview(path: String, max_rows: usize, python_path: Option<String>) -> Result<CsvPreview, String> {
+    let python = python_path
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "python".to_string());
+    let params = serde_json::json!({ "path": path, "max_rows": max_rows });
+    if let Ok(value) = worker::call(&python, "read_csv_preview", params) {
+        if let Ok(preview) = serde_json::from_value::<CsvPreview>(value) {
+            return Ok(preview);
+        }
+    }
+
+    let mut reader = csv::Reader::from_path(path).map_err(|err| err.to_string())?;
+    let headers = reader
+        .headers()
+        .map_err(|err| err.to_string())?
+        .iter()
+        .map(std::string::ToString::to_string)
+        .collect::<Vec<String>>();
+
+    let rows = reader
+        .records()
+        .take(max_rows)
+        .flatten()
+        .map(|record| record.iter().map(std::string::ToString::to_string).collect::<Vec<String>>())
+        .collect::<Vec<Vec<String>>>();
+
+    Ok(CsvPreview { headers, rows })
+}
+
+/// Loads job state left behind by a previous session for every known
+/// project, so `get_run_job_status`/`cancel_run_job` can see those jobs
+/// immediately instead of only after the frontend happens to call
+/// `list_run_jobs` first. Seeds `JOB_COUNTER` past the highest reloaded id so
+/// a newly created job can never collide with one reloaded from disk, and
+/// reconciles any job left `Queued`/`Running` by a session that never
+/// recorded its outcome.
+fn reload_jobs_from_previous_sessions() {
+    if let Ok(projects) = discover_projects_local() {
+        for project in projects {
+            jobs::reload_project(&project.path);
+        }
+    }
+    jobs::reconcile_stale_records();
+    JOB_COUNTER.store(jobs::max_job_id() + 1, Ordering::Relaxed);
 }
 
 fn main() {
-    tauri::Builder::default()
-        .plugin(tauri_plugin_opener::init())
+    reload_jobs_from_previous_sessions();
+
+    tauri::Builder::default()
+        .plugin(tauri_plugin_opener::init())
         .invoke_handler(tauri::generate_handler![
             discover_projects,
             run_moldock,
-            detect_python_path_cmd,
+            detect_backends,
             open_in_explorer,
             read_text_file,
             read_progress_file,
             get_run_job_status,
+            get_run_job_output,
+            get_run_job_result,
+            cancel_run_job,
+            list_run_jobs,
             read_csv_preview,
-            run_moldock_async
+            run_moldock_async,
+            worker_call,
+            watch_project,
+            stop_watch_project,
+            run_pipeline_script
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
-}
+        .run(tauri::generate_context!())
+        .expect("error while running tauri application");
+}
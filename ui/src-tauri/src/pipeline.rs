@@ -0,0 +1,109 @@
+//! Embedded Lua runtime for multi-stage pipeline scripts.
+//!
+//! A single `moldockpipe.cli` invocation per job can't express a workflow
+//! like prep -> dock -> rescore -> filter with conditional branching, so a
+//! project can ship a `pipeline.lua` script that drives it: a host `run`
+//! function launches each CLI step and returns its exit status/output, and
+//! `read_result_csv`/`read_progress` let the script inspect prior output to
+//! decide whether to run later steps. Projects without their own script fall
+//! back to the embedded default workflow below.
+
+use mlua::{Lua, LuaSerdeExt, Table};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::with_repo_pythonpath;
+
+const DEFAULT_PIPELINE: &[u8] = include_bytes!("../assets/default_pipeline.lua");
+
+fn install_run(lua: &Lua, python: String, project_dir: String) -> mlua::Result<()> {
+    let run_fn = lua.create_function(move |lua, (args, opts): (Vec<String>, Option<Table>)| {
+        let stop_file = PathBuf::from(&project_dir).join("state").join("stop_progress_watcher");
+        if stop_file.exists() {
+            return Err(mlua::Error::RuntimeError("Pipeline canceled by user.".to_string()));
+        }
+
+        let cwd = opts
+            .as_ref()
+            .and_then(|table| table.get::<_, Option<String>>("cwd").ok().flatten())
+            .unwrap_or_else(|| project_dir.clone());
+
+        let mut command = Command::new(&python);
+        command.current_dir(&cwd);
+        with_repo_pythonpath(&mut command);
+        command.arg("-m").arg("moldockpipe.cli").args(&args);
+
+        let output = command
+            .output()
+            .map_err(|err| mlua::Error::RuntimeError(format!("Could not launch pipeline step: {}", err)))?;
+
+        let result = lua.create_table()?;
+        result.set("exit_status", output.status.code().unwrap_or(1))?;
+        result.set("stdout", String::from_utf8_lossy(&output.stdout).to_string())?;
+        result.set("stderr", String::from_utf8_lossy(&output.stderr).to_string())?;
+        Ok(result)
+    })?;
+    lua.globals().set("run", run_fn)
+}
+
+fn install_read_result_csv(lua: &Lua, project_dir: String) -> mlua::Result<()> {
+    let read_csv_fn = lua.create_function(move |lua, relative_path: String| {
+        let full_path = PathBuf::from(&project_dir).join(&relative_path);
+        let mut reader = csv::Reader::from_path(&full_path)
+            .map_err(|err| mlua::Error::RuntimeError(format!("Could not read {}: {}", full_path.display(), err)))?;
+        let headers: Vec<String> = reader
+            .headers()
+            .map_err(|err| mlua::Error::RuntimeError(err.to_string()))?
+            .iter()
+            .map(str::to_string)
+            .collect();
+
+        let rows = lua.create_table()?;
+        for (row_index, record) in reader.records().flatten().enumerate() {
+            let row = lua.create_table()?;
+            for (column_index, value) in record.iter().enumerate() {
+                if let Some(header) = headers.get(column_index) {
+                    row.set(header.as_str(), value)?;
+                }
+            }
+            rows.set(row_index + 1, row)?;
+        }
+        Ok(rows)
+    })?;
+    lua.globals().set("read_result_csv", read_csv_fn)
+}
+
+fn install_read_progress(lua: &Lua, project_dir: String) -> mlua::Result<()> {
+    let read_progress_fn = lua.create_function(move |lua, ()| {
+        let progress_path = PathBuf::from(&project_dir).join("state").join("progress.json");
+        let contents = fs::read_to_string(&progress_path).unwrap_or_else(|_| "{}".to_string());
+        let value: serde_json::Value = serde_json::from_str(&contents).unwrap_or(serde_json::Value::Null);
+        lua.to_value(&value)
+    })?;
+    lua.globals().set("read_progress", read_progress_fn)
+}
+
+/// Runs `script_path` if given, otherwise `{project_dir}/pipeline.lua` if the
+/// project has one, otherwise the embedded default workflow, executing each
+/// `run(...)` step sequentially.
+pub fn run_script(python: &str, project_dir: &str, script_path: Option<&Path>) -> Result<(), String> {
+    let project_script = PathBuf::from(project_dir).join("pipeline.lua");
+    let source: Vec<u8> = if let Some(path) = script_path {
+        fs::read(path).map_err(|err| format!("Could not read pipeline script {}: {}", path.display(), err))?
+    } else if project_script.exists() {
+        fs::read(&project_script)
+            .map_err(|err| format!("Could not read pipeline script {}: {}", project_script.display(), err))?
+    } else {
+        DEFAULT_PIPELINE.to_vec()
+    };
+
+    let lua = Lua::new();
+    install_run(&lua, python.to_string(), project_dir.to_string()).map_err(|err| err.to_string())?;
+    install_read_result_csv(&lua, project_dir.to_string()).map_err(|err| err.to_string())?;
+    install_read_progress(&lua, project_dir.to_string()).map_err(|err| err.to_string())?;
+
+    lua.load(&source)
+        .exec()
+        .map_err(|err| format!("Pipeline script failed: {}", err))
+}
@@ -0,0 +1,146 @@
+//! Watch mode: re-run the pipeline automatically when project inputs change.
+//!
+//! Mirrors the debounced recursive watcher a file-driven test runner would
+//! use: a `notify` watcher feeds raw filesystem events to a background
+//! thread, which coalesces bursts of edits (~300ms) before firing
+//! `run_moldock_async` again. Writes under `state/` (progress files, job
+//! records) and result CSVs are ignored so a run's own output doesn't
+//! trigger another run.
+
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+struct WatchHandle {
+    stop: Arc<AtomicBool>,
+}
+
+static WATCHERS: OnceLock<Mutex<HashMap<String, WatchHandle>>> = OnceLock::new();
+
+fn watchers_map() -> &'static Mutex<HashMap<String, WatchHandle>> {
+    WATCHERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+#[derive(Clone, Serialize)]
+struct WatchTriggeredEvent {
+    project_dir: String,
+}
+
+/// Generated outputs (job state, progress file, result CSVs) live under
+/// `state/` or `results/`; re-triggering a run because of those would create
+/// an infinite watch -> run -> output -> watch loop. Scoped to those
+/// directories rather than matching any `.csv` file, since a project's own
+/// ligand/receptor input list may itself be CSV-formatted.
+fn is_generated_output(project_root: &Path, path: &Path) -> bool {
+    if let Ok(relative) = path.strip_prefix(project_root) {
+        return relative.starts_with("state") || relative.starts_with("results");
+    }
+    false
+}
+
+/// Starts watching `project_dir` for input changes, re-running
+/// `run_moldock_async(args)` after a debounce window. A no-op if the project
+/// is already being watched.
+pub fn start(app: AppHandle, project_dir: String, args: Vec<String>) -> Result<(), String> {
+    let mut watchers = watchers_map()
+        .lock()
+        .map_err(|_| "Failed to lock watch registry.".to_string())?;
+    if watchers.contains_key(&project_dir) {
+        return Ok(());
+    }
+
+    let root = PathBuf::from(&project_dir);
+    let (tx, rx) = channel::<Event>();
+    let mut watcher: RecommendedWatcher = notify::recommended_watcher(move |result: notify::Result<Event>| {
+        if let Ok(event) = result {
+            let _ = tx.send(event);
+        }
+    })
+    .map_err(|err| format!("Could not create file watcher: {}", err))?;
+    watcher
+        .watch(&root, RecursiveMode::Recursive)
+        .map_err(|err| format!("Could not watch project directory: {}", err))?;
+
+    let stop_flag = Arc::new(AtomicBool::new(false));
+    let stop_flag_thread = stop_flag.clone();
+    let root_thread = root.clone();
+    let project_dir_thread = project_dir.clone();
+
+    thread::spawn(move || {
+        // Keep the watcher alive for as long as this thread runs; dropping
+        // it tears down the underlying OS watch.
+        let _watcher = watcher;
+        let mut changed: HashSet<PathBuf> = HashSet::new();
+        let mut last_change: Option<Instant> = None;
+        let mut in_flight_job_id: Option<u64> = None;
+
+        while !stop_flag_thread.load(Ordering::Relaxed) {
+            match rx.recv_timeout(Duration::from_millis(100)) {
+                Ok(event) => {
+                    for path in event.paths {
+                        if !is_generated_output(&root_thread, &path) {
+                            changed.insert(path);
+                            last_change = Some(Instant::now());
+                        }
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+
+            // This is the expected case, not an edge case: a user tuning
+            // parameters while docking runs will edit `config/` mid-run.
+            // Don't let that spawn a second `moldockpipe.cli` racing the
+            // first one on `state/progress.json` and `results/` output.
+            let previous_run_finished = in_flight_job_id.map_or(true, |job_id| {
+                !matches!(
+                    crate::jobs::get(job_id).map(|record| record.state),
+                    Some(crate::jobs::JobState::Queued) | Some(crate::jobs::JobState::Running)
+                )
+            });
+
+            let should_fire = previous_run_finished
+                && last_change
+                    .map(|at| !changed.is_empty() && at.elapsed() >= DEBOUNCE)
+                    .unwrap_or(false);
+            if should_fire {
+                changed.clear();
+                last_change = None;
+                let _ = app.emit(
+                    "watch://run-triggered",
+                    WatchTriggeredEvent {
+                        project_dir: project_dir_thread.clone(),
+                    },
+                );
+                match crate::run_moldock_async(app.clone(), args.clone(), Some(project_dir_thread.clone()), None, None) {
+                    Ok(job) => in_flight_job_id = Some(job.job_id),
+                    Err(_) => in_flight_job_id = None,
+                }
+            }
+        }
+    });
+
+    watchers.insert(project_dir, WatchHandle { stop: stop_flag });
+    Ok(())
+}
+
+/// Tears down the watcher thread for `project_dir`, if any is running.
+pub fn stop(project_dir: &str) -> Result<(), String> {
+    let mut watchers = watchers_map()
+        .lock()
+        .map_err(|_| "Failed to lock watch registry.".to_string())?;
+    if let Some(handle) = watchers.remove(project_dir) {
+        handle.stop.store(true, Ordering::Relaxed);
+    }
+    Ok(())
+}
@@ -0,0 +1,142 @@
+//! Long-lived `python -m moldockpipe.rpc` worker.
+//!
+//! Spawning a fresh Python interpreter per command pays full interpreter and
+//! import startup cost (RDKit/numpy in particular). Instead we keep one child
+//! process alive and speak newline-delimited JSON-RPC over its stdin/stdout:
+//! one `{method, params, id}` object per line in, one response object per
+//! line out.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use crate::with_repo_pythonpath;
+
+#[derive(Serialize)]
+struct JsonRpcRequest {
+    method: String,
+    params: Value,
+    id: u64,
+}
+
+struct Worker {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+static WORKER: OnceLock<Mutex<Option<Worker>>> = OnceLock::new();
+static NEXT_REQUEST_ID: AtomicU64 = AtomicU64::new(1);
+
+fn worker_slot() -> &'static Mutex<Option<Worker>> {
+    WORKER.get_or_init(|| Mutex::new(None))
+}
+
+fn spawn_worker(python: &str) -> Result<Worker, String> {
+    let mut command = std::process::Command::new(python);
+    command
+        .arg("-m")
+        .arg("moldockpipe.rpc")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null());
+    with_repo_pythonpath(&mut command);
+
+    let mut child = command.spawn().map_err(|err| {
+        format!(
+            "Could not start moldockpipe.rpc worker. Verify Python path and module availability (moldockpipe.rpc). Details: {}",
+            err
+        )
+    })?;
+    let stdin = child.stdin.take().ok_or("Worker process has no stdin pipe.")?;
+    let stdout = child.stdout.take().ok_or("Worker process has no stdout pipe.")?;
+    Ok(Worker {
+        child,
+        stdin,
+        stdout: BufReader::new(stdout),
+    })
+}
+
+/// A failure talking to the worker process itself (broken pipe, process
+/// exited) as opposed to an application-level error the worker responded
+/// with. Only the former means the process needs to be respawned.
+enum CallError {
+    PipeClosed(String),
+    Rpc(String),
+}
+
+fn send_request(worker: &mut Worker, method: &str, params: &Value) -> Result<Value, CallError> {
+    let id = NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed);
+    let request = JsonRpcRequest {
+        method: method.to_string(),
+        params: params.clone(),
+        id,
+    };
+    let mut line = serde_json::to_string(&request).map_err(|err| CallError::PipeClosed(err.to_string()))?;
+    line.push('\n');
+    worker
+        .stdin
+        .write_all(line.as_bytes())
+        .map_err(|err| CallError::PipeClosed(format!("Worker pipe write failed: {}", err)))?;
+    worker
+        .stdin
+        .flush()
+        .map_err(|err| CallError::PipeClosed(format!("Worker pipe flush failed: {}", err)))?;
+
+    let mut response_line = String::new();
+    let bytes_read = worker
+        .stdout
+        .read_line(&mut response_line)
+        .map_err(|err| CallError::PipeClosed(format!("Worker pipe read failed: {}", err)))?;
+    if bytes_read == 0 {
+        return Err(CallError::PipeClosed("Worker pipe closed (process likely crashed).".to_string()));
+    }
+
+    let mut response: Value = serde_json::from_str(response_line.trim())
+        .map_err(|err| CallError::PipeClosed(format!("Worker returned invalid JSON: {}", err)))?;
+    if let Some(error) = response.get("error").filter(|value| !value.is_null()) {
+        return Err(CallError::Rpc(error.to_string()));
+    }
+    Ok(response["result"].take())
+}
+
+fn kill_worker(worker: &mut Worker) {
+    let _ = worker.child.kill();
+    let _ = worker.child.wait();
+}
+
+/// Calls `method` on the shared worker, starting it on first use. If the pipe
+/// turns out to be dead (the worker crashed since the last call), the dead
+/// process is dropped and a replacement is spawned transparently so the next
+/// call succeeds; application-level RPC errors are returned as-is and do not
+/// trigger a respawn, since the worker itself is still healthy.
+pub fn call(python: &str, method: &str, params: Value) -> Result<Value, String> {
+    let mut slot = worker_slot()
+        .lock()
+        .map_err(|_| "Failed to lock worker process.".to_string())?;
+
+    if slot.is_none() {
+        *slot = Some(spawn_worker(python)?);
+    }
+
+    match send_request(slot.as_mut().unwrap(), method, &params) {
+        Ok(value) => Ok(value),
+        Err(CallError::Rpc(message)) => Err(message),
+        Err(CallError::PipeClosed(message)) => {
+            if let Some(mut dead) = slot.take() {
+                kill_worker(&mut dead);
+            }
+            *slot = Some(spawn_worker(python)?);
+            Err(message)
+        }
+    }
+}
+
+/// Health-checks the worker by asking it to respond to a `ping` call,
+/// (re)starting the process if it is not already running.
+pub fn is_alive(python: &str) -> bool {
+    call(python, "ping", Value::Null).is_ok()
+}